@@ -0,0 +1,387 @@
+use crate::FileType;
+use crate::Position;
+use crate::Row;
+use crate::SearchDirection;
+use std::fs;
+use std::io::{Error, Write};
+use std::time::Instant;
+
+#[derive(PartialEq, Clone, Copy)]
+enum CharClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+fn classify(c: char, long: bool) -> CharClass {
+    if c.is_whitespace() {
+        CharClass::Whitespace
+    } else if long || c.is_alphanumeric() || c == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+#[derive(Default)]
+pub struct Document {
+    rows: Vec<Row>,
+    pub file_name: Option<String>,
+    dirty: bool,
+    file_type: FileType,
+    last_edit: Option<Instant>,
+}
+
+impl Document {
+    /// # Errors
+    /// Returns an error if `filename` cannot be read.
+    pub fn open(filename: &str) -> Result<Self, std::io::Error> {
+        let file_type = FileType::from(filename);
+        let contents = fs::read_to_string(filename)?;
+        let mut rows = Vec::new();
+        for value in contents.lines() {
+            let mut row = Row::from(value);
+            row.highlight(file_type.highlighting_options(), None);
+            rows.push(row);
+        }
+        Ok(Self {
+            rows,
+            file_name: Some(filename.to_string()),
+            dirty: false,
+            file_type,
+            last_edit: None,
+        })
+    }
+
+    /// When the most recent insert/delete happened, or `None` if the
+    /// document has no unsaved edits. Cleared by `save`.
+    #[must_use]
+    pub fn last_edit(&self) -> Option<Instant> {
+        self.last_edit
+    }
+
+    #[must_use]
+    pub fn file_type(&self) -> String {
+        self.file_type.name()
+    }
+
+    pub fn highlight(&mut self, word: Option<&str>) {
+        for row in &mut self.rows {
+            row.highlight(self.file_type.highlighting_options(), word);
+        }
+    }
+
+    #[must_use]
+    pub fn row(&self, index: usize) -> Option<&Row> {
+        self.rows.get(index)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn insert(&mut self, at: &Position, c: char) {
+        if at.y > self.rows.len() {
+            return;
+        }
+        self.dirty = true;
+        self.last_edit = Some(Instant::now());
+        if c == '\n' {
+            self.insert_newline(at);
+            return;
+        }
+        if at.y == self.rows.len() {
+            let mut row = Row::default();
+            row.insert(0, c);
+            row.highlight(self.file_type.highlighting_options(), None);
+            self.rows.push(row);
+        } else {
+            let row = &mut self.rows[at.y];
+            row.insert(at.x, c);
+            row.highlight(self.file_type.highlighting_options(), None);
+        }
+    }
+
+    fn insert_newline(&mut self, at: &Position) {
+        if at.y > self.rows.len() {
+            return;
+        }
+        if at.y == self.rows.len() {
+            self.rows.push(Row::default());
+            return;
+        }
+        let mut new_row = self.rows[at.y].split(at.x);
+        self.rows[at.y].highlight(self.file_type.highlighting_options(), None);
+        new_row.highlight(self.file_type.highlighting_options(), None);
+        self.rows.insert(at.y + 1, new_row);
+    }
+
+    pub fn delete(&mut self, at: &Position) {
+        let len = self.rows.len();
+        if at.y >= len {
+            return;
+        }
+        self.dirty = true;
+        self.last_edit = Some(Instant::now());
+        if at.x == self.rows[at.y].len() && at.y + 1 < len {
+            let next_row = self.rows.remove(at.y + 1);
+            let row = &mut self.rows[at.y];
+            row.append(&next_row);
+        } else {
+            let row = &mut self.rows[at.y];
+            row.delete(at.x);
+        }
+        self.rows[at.y].highlight(self.file_type.highlighting_options(), None);
+    }
+
+    /// # Errors
+    /// Returns an error if the document has a file name and writing to it
+    /// fails.
+    pub fn save(&mut self) -> Result<(), Error> {
+        if let Some(file_name) = &self.file_name {
+            let mut file = fs::File::create(file_name)?;
+            for row in &self.rows {
+                file.write_all(row.as_bytes())?;
+                file.write_all(b"\n")?;
+            }
+            self.dirty = false;
+            self.last_edit = None;
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn is_empty_line(&self, position: &Position) -> bool {
+        position.x == 0 && self.row(position.y).is_some_and(Row::is_empty)
+    }
+
+    fn char_class(&self, position: &Position, long: bool) -> CharClass {
+        self.row(position.y)
+            .and_then(|row| row.char_at(position.x))
+            .map_or(CharClass::Whitespace, |c| classify(c, long))
+    }
+
+    /// The position one character after `position`, stepping onto the next
+    /// row once the current one is exhausted. `None` at the end of the
+    /// document.
+    #[must_use]
+    pub fn next_position(&self, position: Position) -> Option<Position> {
+        let row_len = self.row(position.y).map_or(0, Row::len);
+        if position.x < row_len {
+            Some(Position { x: position.x + 1, y: position.y })
+        } else if position.y + 1 < self.len() {
+            Some(Position { x: 0, y: position.y + 1 })
+        } else {
+            None
+        }
+    }
+
+    fn retreat(&self, position: Position) -> Option<Position> {
+        if position.x > 0 {
+            Some(Position { x: position.x - 1, y: position.y })
+        } else if position.y > 0 {
+            let prev_len = self.row(position.y - 1).map_or(0, Row::len);
+            Some(Position { x: prev_len, y: position.y - 1 })
+        } else {
+            None
+        }
+    }
+
+    /// The next "word start" from `from`, vim's `w`/`W` motion: first skips
+    /// the remainder of the word `from` sits in (if any), then skips
+    /// whitespace, stopping early on an empty line either way.
+    #[must_use]
+    pub fn next_word_start(&self, from: Position, long: bool) -> Position {
+        let mut cur = from;
+        let starting_class = self.char_class(&cur, long);
+        if starting_class != CharClass::Whitespace {
+            loop {
+                match self.next_position(cur) {
+                    Some(next) => {
+                        cur = next;
+                        if self.is_empty_line(&cur) {
+                            return cur;
+                        }
+                        if self.char_class(&cur, long) != starting_class {
+                            break;
+                        }
+                    },
+                    None => return cur,
+                }
+            }
+        }
+        loop {
+            if self.is_empty_line(&cur) || self.char_class(&cur, long) != CharClass::Whitespace {
+                return cur;
+            }
+            match self.next_position(cur) {
+                Some(next) => cur = next,
+                None => return cur,
+            }
+        }
+    }
+
+    /// The previous "word start" from `from`, vim's `b`/`B` motion.
+    #[must_use]
+    pub fn prev_word_start(&self, from: Position, long: bool) -> Position {
+        let Some(mut cur) = self.retreat(from) else {
+            return from;
+        };
+        loop {
+            if self.is_empty_line(&cur) {
+                return cur;
+            }
+            if self.char_class(&cur, long) != CharClass::Whitespace {
+                break;
+            }
+            match self.retreat(cur) {
+                Some(p) => cur = p,
+                None => return cur,
+            }
+        }
+        let class = self.char_class(&cur, long);
+        while let Some(p) = self.retreat(cur) {
+            if self.is_empty_line(&p) || self.char_class(&p, long) != class {
+                break;
+            }
+            cur = p;
+        }
+        cur
+    }
+
+    /// The next "word end" from `from`, vim's `e`/`E` motion.
+    #[must_use]
+    pub fn next_word_end(&self, from: Position, long: bool) -> Position {
+        let Some(mut cur) = self.next_position(from) else {
+            return from;
+        };
+        loop {
+            if self.is_empty_line(&cur) {
+                return cur;
+            }
+            if self.char_class(&cur, long) != CharClass::Whitespace {
+                break;
+            }
+            match self.next_position(cur) {
+                Some(p) => cur = p,
+                None => return cur,
+            }
+        }
+        let class = self.char_class(&cur, long);
+        while let Some(p) = self.next_position(cur) {
+            if self.is_empty_line(&p) || self.char_class(&p, long) != class {
+                break;
+            }
+            cur = p;
+        }
+        cur
+    }
+
+    #[must_use]
+    pub fn find(&self, query: &str, at: &Position, direction: SearchDirection) -> Option<Position> {
+        if at.y >= self.rows.len() {
+            return None;
+        }
+        let mut position = Position { x: at.x, y: at.y };
+
+        let start = if direction == SearchDirection::Forward {
+            at.y
+        } else {
+            0
+        };
+        let end = if direction == SearchDirection::Forward {
+            self.rows.len()
+        } else {
+            at.y.saturating_add(1)
+        };
+
+        for _ in start..end {
+            if let Some(row) = self.rows.get(position.y) {
+                if let Some(x) = row.find(query, position.x, direction) {
+                    position.x = x;
+                    return Some(position);
+                }
+                if direction == SearchDirection::Forward {
+                    position.y = position.y.saturating_add(1);
+                    position.x = 0;
+                } else {
+                    position.y = position.y.saturating_sub(1);
+                    position.x = self.rows[position.y].len();
+                }
+            } else {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(lines: &[&str]) -> Document {
+        let mut doc = Document::default();
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                doc.insert(&Position { x, y }, c);
+            }
+            if y + 1 < lines.len() {
+                doc.insert(&Position { x: line.chars().count(), y }, '\n');
+            }
+        }
+        doc
+    }
+
+    #[test]
+    fn next_word_start_stops_at_a_punctuation_boundary_for_short_words() {
+        let doc = build(&["foo.bar"]);
+        let next = doc.next_word_start(Position { x: 0, y: 0 }, false);
+        assert_eq!((next.x, next.y), (3, 0)); // short `w` treats '.' as its own word
+    }
+
+    #[test]
+    fn next_word_start_skips_punctuation_entirely_for_long_words() {
+        let doc = build(&["foo.bar"]);
+        let next = doc.next_word_start(Position { x: 0, y: 0 }, true);
+        assert_eq!((next.x, next.y), (7, 0)); // long `W` has no more than one WORD here
+    }
+
+    #[test]
+    fn next_word_start_stops_on_an_empty_line() {
+        let doc = build(&["foo", "", "bar"]);
+        let next = doc.next_word_start(Position { x: 0, y: 0 }, false);
+        assert_eq!((next.x, next.y), (0, 1));
+    }
+
+    #[test]
+    fn prev_word_start_stops_on_an_empty_line() {
+        let doc = build(&["foo", "", "bar"]);
+        let prev = doc.prev_word_start(Position { x: 0, y: 2 }, false);
+        assert_eq!((prev.x, prev.y), (0, 1));
+    }
+
+    #[test]
+    fn next_word_end_stops_at_a_punctuation_boundary_for_short_words() {
+        let doc = build(&["foo.bar"]);
+        let end = doc.next_word_end(Position { x: 0, y: 0 }, false);
+        assert_eq!((end.x, end.y), (2, 0)); // end of "foo", just before '.'
+    }
+
+    #[test]
+    fn next_word_end_treats_an_entire_long_word_as_one_unit() {
+        let doc = build(&["foo.bar"]);
+        let end = doc.next_word_end(Position { x: 0, y: 0 }, true);
+        assert_eq!((end.x, end.y), (6, 0)); // end of the whole WORD "foo.bar"
+    }
+}
@@ -1,21 +1,54 @@
+use crate::highlighting;
 use crate::Document;
+use crate::InputEvent;
 use crate::Row;
 use crate::Terminal;
+use std::cmp;
 use std::env;
 use std::time::Duration;
 use std::time::Instant;
+use crossterm::cursor::SetCursorStyle;
 use crossterm::style::Color;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind, MouseButton};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const QUIT_TIMES: u8 = 3;
+const TAB_STOP: usize = 4;
+const AUTOSAVE_IDLE: Duration = Duration::from_secs(30);
+const AUTOSAVE_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct Position {
     pub x: usize,
     pub y: usize,
 }
 
+#[derive(PartialEq, Clone, Copy)]
+pub enum SearchDirection {
+    Forward,
+    Backward,
+}
+
+#[derive(Default)]
+pub struct CommandState {
+    buffer: String,
+    cursor: usize,
+}
+
+pub enum Mode {
+    Normal,
+    Insert,
+    Visual { anchor: Position },
+    Command(CommandState),
+}
+
+#[derive(Clone, Copy)]
+enum WordMotion {
+    NextStart,
+    PrevStart,
+    NextEnd,
+}
+
 struct StatusMessage {
     text: String,
     time: Instant,
@@ -38,19 +71,25 @@ pub struct Editor {
     document: Document,
     status_message: StatusMessage,
     quit_times: u8,
+    mode: Mode,
+    tab_stop: usize,
 }
 
 impl Editor {
     pub fn run(&mut self) {
+        let mut needs_redraw = true;
         loop {
-            if let Err(error) = self.refresh_screen() {
-                die(error);
+            if needs_redraw {
+                if let Err(error) = self.refresh_screen() {
+                    die(&error);
+                }
             }
             if self.should_quit {
                 break;
             }
-            if let Err(error) = self.process_keypress() {
-                die(error);
+            match self.process_keypress() {
+                Ok(redraw) => needs_redraw = redraw,
+                Err(error) => die(&error),
             }
         }
     }
@@ -60,24 +99,24 @@ impl Editor {
         let mut initial_status = String::from("Help: Ctrl-S = save | Ctrl-Q = quit");
         let document = if args.len() > 1 {
             let file_name = &args[1];
-            let doc = Document::open(&file_name);
-            if doc.is_ok() {
-                doc.unwrap()
-            } else {
-                initial_status = format!("ERR: Could not open file: {}", file_name);
+            Document::open(file_name).unwrap_or_else(|_| {
+                initial_status = format!("ERR: Could not open file: {file_name}");
                 Document::default()
-            }
+            })
         } else {
             Document::default()
         };
-        Self { 
+        Terminal::set_cursor_style(SetCursorStyle::SteadyBlock).unwrap_or_default();
+        Self {
             should_quit: false,
-            terminal: Terminal::default().expect("Failed to initialize terminal"),
+            terminal: Terminal::new().expect("Failed to initialize terminal"),
             document,
             cursor_position: Position::default(),
             offset: Position::default(),
             status_message: StatusMessage::from(initial_status),
-            quit_times: QUIT_TIMES
+            quit_times: QUIT_TIMES,
+            mode: Mode::Normal,
+            tab_stop: TAB_STOP,
         }
     }
 
@@ -92,9 +131,9 @@ impl Editor {
             self.draw_status_bar();
             self.draw_message_bar();
             Terminal::cursor_position(&Position {
-                 x: self.cursor_position.x.saturating_sub(self.offset.x), 
+                 x: self.render_x().saturating_sub(self.offset.x),
                  y: self.cursor_position.y.saturating_sub(self.offset.y),
-            })?; 
+            })?;
         }
         Terminal::flush()?;
         Ok(())
@@ -102,7 +141,7 @@ impl Editor {
 
     fn save(&mut self) {
         if self.document.file_name.is_none() {
-            let new_name = self.prompt("Save as: ").unwrap_or(None);
+            let new_name = self.prompt("Save as: ", |_, _, _| {}).unwrap_or(None);
             if new_name.is_none() {
                 self.status_message = StatusMessage::from("Save aborted.".to_string());
                 return;
@@ -117,107 +156,413 @@ impl Editor {
         }
     }
 
-    fn process_keypress(&mut self) -> Result<(), std::io::Error> {
-        let KeyEvent { code, modifiers, .. } = Terminal::read_key()?;
-    
+    /// Handles one input poll. Returns whether the screen needs to be
+    /// redrawn — `true` for an actual key/mouse event, or for an idle tick
+    /// that triggered an autosave; `false` for an idle tick that did
+    /// nothing, so the autosave poll doesn't flicker the screen.
+    fn process_keypress(&mut self) -> Result<bool, std::io::Error> {
+        let Some(event) = Terminal::read_key_timeout(AUTOSAVE_POLL_INTERVAL)? else {
+            return Ok(self.maybe_autosave());
+        };
+        let quit_times_before = self.quit_times;
+        let is_wheel_scroll = matches!(
+            event,
+            InputEvent::Mouse(MouseEvent { kind: MouseEventKind::ScrollDown | MouseEventKind::ScrollUp, .. })
+        );
+        match event {
+            InputEvent::Key(KeyEvent { code, modifiers, .. }) => self.process_key(code, modifiers),
+            InputEvent::Mouse(mouse_event) => self.process_mouse_event(mouse_event),
+        }
+        // A wheel tick moves the viewport, not the cursor, so it must not
+        // be immediately clamped back to the cursor's position.
+        if !is_wheel_scroll {
+            self.scroll();
+        }
+        // Any event other than another quit attempt cancels a pending quit.
+        if self.quit_times == quit_times_before && self.quit_times < QUIT_TIMES {
+            self.quit_times = QUIT_TIMES;
+            self.status_message = StatusMessage::from(String::new());
+        }
+        self.maybe_autosave();
+        Ok(true)
+    }
+
+    /// Silently saves the document once it has sat dirty and untouched for
+    /// `AUTOSAVE_IDLE`. Never fires on a file with no name, since there is
+    /// nowhere to save it to without prompting the user. Returns whether it
+    /// saved, so an idle caller knows whether the status bar needs a redraw.
+    fn maybe_autosave(&mut self) -> bool {
+        if self.document.file_name.is_none() {
+            return false;
+        }
+        let idle_long_enough = self
+            .document
+            .last_edit()
+            .is_some_and(|last_edit| last_edit.elapsed() >= AUTOSAVE_IDLE);
+        if !idle_long_enough {
+            return false;
+        }
+        if self.document.save().is_ok() {
+            self.status_message = StatusMessage::from("Autosaved.".to_string());
+            true
+        } else {
+            false
+        }
+    }
+
+    fn process_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
         match (code, modifiers) {
-            (KeyCode::Char('q'), KeyModifiers::CONTROL) => {
-                if self.quit_times > 0 && self.document.is_dirty() {
-                    self.status_message = StatusMessage::from(format!(
-                        "WARNING! File has unsaved changes. Press Ctrl-Q {} more times to quit.",
-                        self.quit_times
-                    ));
-                    self.quit_times -= 1;
-                    return Ok(());
+            (KeyCode::Char('q'), KeyModifiers::CONTROL) => self.attempt_quit(),
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => self.save(),
+            (KeyCode::Char('f'), KeyModifiers::CONTROL) => self.search(),
+            (KeyCode::Left, KeyModifiers::CONTROL) => self.move_by_word(WordMotion::PrevStart, false),
+            (KeyCode::Right, KeyModifiers::CONTROL) => self.move_by_word(WordMotion::NextStart, false),
+            _ => match self.mode {
+                Mode::Normal => self.process_normal_keypress(code, modifiers),
+                Mode::Insert => self.process_insert_keypress(code, modifiers),
+                Mode::Visual { .. } => self.process_visual_keypress(code, modifiers),
+                Mode::Command(_) => self.process_command_keypress(code, modifiers),
+            },
+        }
+    }
+
+    fn process_mouse_event(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.cursor_position = self.clamp_click_position(event.column, event.row);
+            },
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let position = self.clamp_click_position(event.column, event.row);
+                if !matches!(self.mode, Mode::Visual { .. }) {
+                    self.mode = Mode::Visual { anchor: self.cursor_position };
                 }
-                self.should_quit = true
+                self.cursor_position = position;
             },
-            (KeyCode::Char('s'), KeyModifiers::CONTROL) => self.save(),
+            MouseEventKind::ScrollDown => {
+                self.offset.y = self.offset.y.saturating_add(3);
+            },
+            MouseEventKind::ScrollUp => {
+                self.offset.y = self.offset.y.saturating_sub(3);
+            },
+            _ => {},
+        }
+    }
+
+    fn clamp_click_position(&self, column: u16, row: u16) -> Position {
+        let height = self.terminal.size().height as usize;
+        let row = (row as usize).min(height.saturating_sub(1));
+        let mut y = self.offset.y.saturating_add(row);
+        if y >= self.document.len() {
+            y = self.document.len().saturating_sub(1);
+        }
+        let target_rx = self.offset.x.saturating_add(column as usize);
+        let x = self
+            .document
+            .row(y)
+            .map_or(0, |doc_row| doc_row.rx_to_cx(target_rx, self.tab_stop));
+        Position { x, y }
+    }
+
+    fn attempt_quit(&mut self) {
+        if self.quit_times > 0 && self.document.is_dirty() {
+            self.status_message = StatusMessage::from(format!(
+                "WARNING! File has unsaved changes. Press Ctrl-Q {} more times to quit.",
+                self.quit_times
+            ));
+            self.quit_times -= 1;
+            return;
+        }
+        self.should_quit = true;
+    }
+
+    fn enter_normal_mode(&mut self) {
+        self.mode = Mode::Normal;
+        Terminal::set_cursor_style(SetCursorStyle::SteadyBlock).unwrap_or_default();
+    }
+
+    fn enter_insert_mode(&mut self) {
+        self.mode = Mode::Insert;
+        Terminal::set_cursor_style(SetCursorStyle::SteadyBar).unwrap_or_default();
+    }
+
+    fn process_normal_keypress(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        if modifiers != KeyModifiers::NONE {
+            return;
+        }
+        match code {
+            KeyCode::Char('i') => self.enter_insert_mode(),
+            KeyCode::Char(':') => {
+                self.mode = Mode::Command(CommandState::default());
+                self.status_message = StatusMessage::from(":".to_string());
+            },
+            KeyCode::Char('v') => self.mode = Mode::Visual { anchor: self.cursor_position },
+            KeyCode::Char('w') => self.move_by_word(WordMotion::NextStart, false),
+            KeyCode::Char('b') => self.move_by_word(WordMotion::PrevStart, false),
+            KeyCode::Char('e') => self.move_by_word(WordMotion::NextEnd, false),
+            KeyCode::Char('W') => self.move_by_word(WordMotion::NextStart, true),
+            KeyCode::Char('B') => self.move_by_word(WordMotion::PrevStart, true),
+            KeyCode::Char('E') => self.move_by_word(WordMotion::NextEnd, true),
+            KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+            | KeyCode::Home
+            | KeyCode::End => self.move_cursor(code),
+            _ => {}
+        }
+    }
+
+    fn process_insert_keypress(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        match (code, modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE) => self.enter_normal_mode(),
             (KeyCode::Char(c), KeyModifiers::NONE) => {
                 self.document.insert(&self.cursor_position, c);
                 self.move_cursor(KeyCode::Right);
             },
+            (KeyCode::Tab, KeyModifiers::NONE) => {
+                self.document.insert(&self.cursor_position, '\t');
+                self.move_cursor(KeyCode::Right);
+            },
             (KeyCode::Delete, KeyModifiers::NONE) => self.document.delete(&self.cursor_position),
-            (KeyCode::Backspace, KeyModifiers::NONE) => {
-                if self.cursor_position.x > 0 || self.cursor_position.y > 0 {
-                    self.move_cursor(KeyCode::Left);
-                    self.document.delete(&self.cursor_position);
-                }
+            (KeyCode::Backspace, KeyModifiers::NONE)
+                if self.cursor_position.x > 0 || self.cursor_position.y > 0 =>
+            {
+                self.move_cursor(KeyCode::Left);
+                self.document.delete(&self.cursor_position);
             },
             (KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right, KeyModifiers::NONE) => self.move_cursor(code),
             _ => {}
         }
-        self.scroll();
-        if self.quit_times < QUIT_TIMES {
-            self.quit_times = QUIT_TIMES;
-            self.status_message = StatusMessage::from(String::new());
+    }
+
+    fn process_visual_keypress(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        if modifiers != KeyModifiers::NONE {
+            return;
+        }
+        match code {
+            KeyCode::Esc => self.enter_normal_mode(),
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => self.move_cursor(code),
+            KeyCode::Char('d') => self.delete_selection(),
+            _ => {}
+        }
+    }
+
+    /// The active Visual-mode selection as an ordered (start, end) pair of
+    /// document positions, inclusive of both endpoints. `None` outside
+    /// Visual mode.
+    fn selection_range(&self) -> Option<(Position, Position)> {
+        match self.mode {
+            Mode::Visual { anchor } => {
+                let anchor_key = (anchor.y, anchor.x);
+                let cursor_key = (self.cursor_position.y, self.cursor_position.x);
+                if anchor_key <= cursor_key {
+                    Some((anchor, self.cursor_position))
+                } else {
+                    Some((self.cursor_position, anchor))
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// The render-column range (exclusive end) of the selection on the
+    /// given row, or `None` if that row has no selected text.
+    fn row_selection_rx(&self, row: &Row, row_index: usize) -> Option<(usize, usize)> {
+        let (start, end) = self.selection_range()?;
+        if row_index < start.y || row_index > end.y {
+            return None;
+        }
+        let start_x = if row_index == start.y { start.x } else { 0 };
+        let end_x = if row_index == end.y { end.x.saturating_add(1) } else { row.len() };
+        let render_start = row.cx_to_rx(start_x, self.tab_stop);
+        let render_end = cmp::max(row.cx_to_rx(end_x, self.tab_stop), render_start.saturating_add(1));
+        Some((render_start, render_end))
+    }
+
+    /// Deletes the characters covered by the active Visual-mode selection
+    /// and returns to Normal mode.
+    fn delete_selection(&mut self) {
+        let Some((start, end)) = self.selection_range() else {
+            return;
+        };
+        let mut count = 1;
+        let mut pos = start;
+        while pos.y != end.y || pos.x != end.x {
+            match self.document.next_position(pos) {
+                Some(next) => pos = next,
+                None => break,
+            }
+            count += 1;
+        }
+        self.cursor_position = start;
+        for _ in 0..count {
+            self.document.delete(&self.cursor_position);
+        }
+        self.enter_normal_mode();
+    }
+
+    fn process_command_keypress(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        if modifiers != KeyModifiers::NONE && modifiers != KeyModifiers::SHIFT {
+            return;
+        }
+        if code == KeyCode::Esc {
+            self.enter_normal_mode();
+            return;
+        }
+        if code == KeyCode::Char('\n') {
+            let command = match &self.mode {
+                Mode::Command(state) => state.buffer.clone(),
+                _ => String::new(),
+            };
+            self.enter_normal_mode();
+            self.execute_command(&command);
+            return;
+        }
+        let Mode::Command(state) = &mut self.mode else {
+            return;
+        };
+        match code {
+            KeyCode::Backspace => {
+                if state.cursor > 0 {
+                    state.cursor -= 1;
+                    state.buffer.remove(state.cursor);
+                }
+            },
+            KeyCode::Left => state.cursor = state.cursor.saturating_sub(1),
+            KeyCode::Right => state.cursor = cmp::min(state.cursor + 1, state.buffer.len()),
+            KeyCode::Char(c) => {
+                state.buffer.insert(state.cursor, c);
+                state.cursor += 1;
+            },
+            _ => return,
+        }
+        let buffer = state.buffer.clone();
+        self.status_message = StatusMessage::from(format!(":{buffer}"));
+    }
+
+    fn execute_command(&mut self, command: &str) {
+        match command {
+            "w" => self.save(),
+            "q" => self.attempt_quit(),
+            "wq" => {
+                self.save();
+                self.attempt_quit();
+            },
+            "" => {},
+            _ => {
+                self.status_message = StatusMessage::from(format!("Unknown command: {command}"));
+            },
         }
-        Ok(())
     }
 
 
-    fn prompt(&mut self, prompt: &str) -> Result<Option<String>, std::io::Error> {
+    fn prompt<C>(&mut self, prompt: &str, mut callback: C) -> Result<Option<String>, std::io::Error>
+    where
+        C: FnMut(&mut Self, KeyEvent, &String),
+    {
         let mut result = String::new();
         loop {
-            self.status_message = StatusMessage::from(format!("{}{}", prompt, result));
+            self.status_message = StatusMessage::from(format!("{prompt}{result}"));
             self.refresh_screen()?;
-            
-            match Terminal::read_key() {
-                Ok(KeyEvent{ code: KeyCode::Backspace, modifiers: KeyModifiers::NONE, .. }) => {
-                    if !result.is_empty() {
-                        result.truncate(result.len() - 1);
-                    }
+
+            let key = Terminal::read_key()?;
+            match key {
+                KeyEvent{ code: KeyCode::Backspace, modifiers: KeyModifiers::NONE, .. } => {
+                    result.truncate(result.len().saturating_sub(1));
                 },
-                Ok(KeyEvent{ code: KeyCode::Char('\n'), modifiers: KeyModifiers::NONE, .. }) => break,
-                Ok(KeyEvent{ code: KeyCode::Char(c), modifiers: KeyModifiers::NONE, .. }) => {
-                    if !c.is_control() {
-                        result.push(c);
-                    }
+                KeyEvent{ code: KeyCode::Char('\n'), modifiers: KeyModifiers::NONE, .. } => break,
+                KeyEvent{ code: KeyCode::Char(c), modifiers: KeyModifiers::NONE, .. } if !c.is_control() => {
+                    result.push(c);
                 },
-                Ok(KeyEvent{ code: KeyCode::Esc, modifiers: KeyModifiers::NONE, .. }) => {
+                KeyEvent{ code: KeyCode::Esc, modifiers: KeyModifiers::NONE, .. } => {
                     result.truncate(0);
                     break;
                 },
                 _ => (),
-            }            
+            }
+            callback(self, key, &result);
         }
         self.status_message = StatusMessage::from(String::new());
         if result.is_empty() {
             return Ok(None);
         }
-        
+
         Ok(Some(result))
     }
-    
+
+    fn search(&mut self) {
+        let old_position = self.cursor_position;
+        let mut direction = SearchDirection::Forward;
+        let query = self
+            .prompt(
+                "Search (Esc to cancel, Arrows to navigate): ",
+                |editor, key, query| {
+                    let mut moved = false;
+                    match key.code {
+                        KeyCode::Right | KeyCode::Down => {
+                            direction = SearchDirection::Forward;
+                            editor.move_cursor(KeyCode::Right);
+                            moved = true;
+                        },
+                        KeyCode::Left | KeyCode::Up => direction = SearchDirection::Backward,
+                        _ => direction = SearchDirection::Forward,
+                    }
+                    if let Some(position) = editor.document.find(query, &editor.cursor_position, direction) {
+                        editor.cursor_position = position;
+                        editor.scroll();
+                    } else if moved {
+                        editor.move_cursor(KeyCode::Left);
+                    }
+                    editor.document.highlight(Some(query));
+                },
+            )
+            .unwrap_or(None);
+
+        if query.is_none() {
+            self.cursor_position = old_position;
+            self.scroll();
+        }
+        self.document.highlight(None);
+    }
+
 
     fn draw_welcome_message(&self) {
-        let mut welcome_message = format!("RTT - Version: {}", VERSION);
+        let mut welcome_message = format!("RTT - Version: {VERSION}");
         let width = self.terminal.size().width as usize;
         let len = welcome_message.len();
         let padding = width.saturating_sub(len) / 2;
         let spaces = " ".repeat(padding.saturating_sub(1));
-        welcome_message = format!("~{}{}", spaces, welcome_message);
+        welcome_message = format!("~{spaces}{welcome_message}");
         welcome_message.truncate(width);
-        println!("{}\r", welcome_message);
-    }    
+        println!("{welcome_message}\r");
+    }
 
     fn scroll(&mut self) {
-        let Position { x, y } = self.cursor_position;
+        let y = self.cursor_position.y;
+        let render_x = self.render_x();
         let width = self.terminal.size().width as usize;
         let height = self.terminal.size().height as usize;
-        let mut offset = &mut self.offset;
+        let offset = &mut self.offset;
         if y < offset.y {
             offset.y = y;
         } else if y >= offset.y.saturating_add(height) {
             offset.y = y.saturating_sub(height).saturating_add(1);
         }
-        if x < offset.x {
-            offset.x = x;
-        } else if x >= offset.x.saturating_add(width) {
-            offset.x = x.saturating_sub(width).saturating_add(1);
+        if render_x < offset.x {
+            offset.x = render_x;
+        } else if render_x >= offset.x.saturating_add(width) {
+            offset.x = render_x.saturating_sub(width).saturating_add(1);
         }
     }
 
+    fn render_x(&self) -> usize {
+        self.document
+            .row(self.cursor_position.y)
+            .map_or(0, |row| row.cx_to_rx(self.cursor_position.x, self.tab_stop))
+    }
+
     fn move_cursor(&mut self, key: KeyCode) {
         let terminal_height = self.terminal.size().height as usize;
         let Position { mut y, mut x } = self.cursor_position;
@@ -229,11 +574,7 @@ impl Editor {
         };
         match key {
             KeyCode::Up => y = y.saturating_sub(1),
-            KeyCode::Down => {
-                if y < height {
-                    y = y.saturating_add(1);
-                }
-            },
+            KeyCode::Down if y < height => y = y.saturating_add(1),
             KeyCode::Left => {
                 if x > 0 {
                     x -= 1;
@@ -254,16 +595,10 @@ impl Editor {
                     x = 0;
                 }
             },
-            KeyCode::PageUp => {
-              y = if y > terminal_height {
-                y - terminal_height
-              } else {
-                0
-              }
-            },
+            KeyCode::PageUp => y = y.saturating_sub(terminal_height),
             KeyCode::PageDown => {
                 y = if y.saturating_add(terminal_height) < height {
-                    y + terminal_height as usize
+                    y + terminal_height
                 } else {
                     height
                 }
@@ -284,20 +619,45 @@ impl Editor {
         self.cursor_position = Position { x, y }
     }
 
-    pub fn draw_row(&self, row: &Row) {
+    fn move_by_word(&mut self, motion: WordMotion, long: bool) {
+        self.cursor_position = match motion {
+            WordMotion::NextStart => self.document.next_word_start(self.cursor_position, long),
+            WordMotion::PrevStart => self.document.prev_word_start(self.cursor_position, long),
+            WordMotion::NextEnd => self.document.next_word_end(self.cursor_position, long),
+        };
+    }
+
+    pub fn draw_row(&self, row: &Row, row_index: usize) {
         let width = self.terminal.size().width as usize;
         let start = self.offset.x;
         let end = self.offset.x + width;
-        let row = row.render(start, end);
-        println!("{}\r", row)
+        let selection = self.row_selection_rx(row, row_index);
+        let mut current_highlighting = highlighting::Type::None;
+        let mut current_selected = false;
+        Terminal::set_foreground_color(current_highlighting.to_color()).unwrap_or_default();
+        for (text, hl_type, selected) in row.render_selected(start, end, self.tab_stop, selection) {
+            if hl_type != current_highlighting {
+                current_highlighting = hl_type;
+                Terminal::set_foreground_color(hl_type.to_color()).unwrap_or_default();
+            }
+            if selected != current_selected {
+                current_selected = selected;
+                let background = if selected { Color::DarkGrey } else { Color::Reset };
+                Terminal::set_background_color(background).unwrap_or_default();
+            }
+            print!("{text}");
+        }
+        Terminal::reset_color().unwrap_or_default();
+        println!("\r");
     }
 
     fn draw_rows(&self) {
         let height = self.terminal.size().height;
         for terminal_row in 0..height {
             Terminal::clear_current_line().unwrap_or_default();
-            if let Some(row) = self.document.row(terminal_row as usize + self.offset.y) {
-                self.draw_row(row);
+            let row_index = terminal_row as usize + self.offset.y;
+            if let Some(row) = self.document.row(row_index) {
+                self.draw_row(row, row_index);
             } else if self.document.is_empty() && terminal_row == height / 3 {
                 self.draw_welcome_message();
             } else {
@@ -316,10 +676,10 @@ impl Editor {
         };
         let mut file_name = "[No Name]".to_string();
         if let Some(name) = &self.document.file_name {
-            file_name = name.clone();
+            name.clone_into(&mut file_name);
             file_name.truncate(20);
         }
-        status = format!("{} - {} lines", file_name, self.document.len());
+        status = format!("{file_name} - {} lines", self.document.len());
         
         let line_indicator = format!(
             "{}/{}",
@@ -331,8 +691,15 @@ impl Editor {
             status.push_str(&" ".repeat(width - len));
         }
 
+        let mode_indicator = match &self.mode {
+            Mode::Normal => "NORMAL",
+            Mode::Insert => "INSERT",
+            Mode::Visual { .. } => "VISUAL",
+            Mode::Command(_) => "COMMAND",
+        };
         status = format!(
-            "{} - {} lines{}",
+            "{} - {} - {} lines{}",
+            mode_indicator,
             file_name,
             self.document.len(),
             modified_indicator
@@ -347,31 +714,31 @@ impl Editor {
         if width > len {
             status.push_str(&" ".repeat(width - len));
         }
-        status = format!("{}{}", status, line_indicator);
+        status = format!("{status}{line_indicator}");
         status.truncate(width);
 
         Terminal::set_background_color(Color::DarkBlue).unwrap_or_default();
         Terminal::set_foreground_color(Color::White).unwrap_or_default();
-        println!("{}\r", status);
+        println!("{status}\r");
         Terminal::reset_color().unwrap_or_default();
     }
 
     fn draw_message_bar(&self) {
         Terminal::clear_current_line().unwrap_or_default();
         let message = &self.status_message;
-        if Instant::now() - message.time < Duration::new(5, 0) {
+        if message.time.elapsed() < Duration::new(5, 0) {
             let mut text = message.text.clone();
             text.truncate(self.terminal.size().width as usize);
-            print!("{}", text);
+            print!("{text}");
         }
     }
 }
 
-fn die(e: std::io::Error) {
+fn die(e: &std::io::Error) {
     Terminal::clear_screen().unwrap_or_else(|_| {
         eprintln!("Failed to clear the terminal");
     });
 
-    panic!("{}", e);
+    panic!("{e}");
 }
 
@@ -0,0 +1,72 @@
+// One bool per highlighting feature a filetype can opt into; a bitflags
+// type would be overkill for four independent toggles on a tutorial-sized
+// crate, so the struct-of-bools shape stays.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default, Clone, Copy)]
+pub struct HighlightingOptions {
+    numbers: bool,
+    strings: bool,
+    comments: bool,
+    keywords: bool,
+}
+
+impl HighlightingOptions {
+    #[must_use]
+    pub fn numbers(self) -> bool {
+        self.numbers
+    }
+    #[must_use]
+    pub fn strings(self) -> bool {
+        self.strings
+    }
+    #[must_use]
+    pub fn comments(self) -> bool {
+        self.comments
+    }
+    #[must_use]
+    pub fn keywords(self) -> bool {
+        self.keywords
+    }
+}
+
+pub struct FileType {
+    name: String,
+    hl_opts: HighlightingOptions,
+}
+
+impl Default for FileType {
+    fn default() -> Self {
+        Self {
+            name: String::from("No filetype"),
+            hl_opts: HighlightingOptions::default(),
+        }
+    }
+}
+
+impl FileType {
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[must_use]
+    pub fn highlighting_options(&self) -> &HighlightingOptions {
+        &self.hl_opts
+    }
+
+    #[must_use]
+    pub fn from(file_name: &str) -> Self {
+        if file_name.to_ascii_lowercase().ends_with(".rs") {
+            return Self {
+                name: String::from("Rust"),
+                hl_opts: HighlightingOptions {
+                    numbers: true,
+                    strings: true,
+                    comments: true,
+                    keywords: true,
+                },
+            };
+        }
+        Self::default()
+    }
+}
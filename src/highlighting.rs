@@ -0,0 +1,25 @@
+use crossterm::style::Color;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Type {
+    None,
+    Number,
+    Match,
+    String,
+    Comment,
+    Keyword,
+}
+
+impl Type {
+    #[must_use]
+    pub fn to_color(self) -> Color {
+        match self {
+            Type::Number => Color::Rgb { r: 220, g: 163, b: 163 },
+            Type::Match => Color::Rgb { r: 38, g: 139, b: 210 },
+            Type::String => Color::Rgb { r: 211, g: 54, b: 130 },
+            Type::Comment => Color::Rgb { r: 133, g: 153, b: 0 },
+            Type::Keyword => Color::Rgb { r: 181, g: 137, b: 0 },
+            Type::None => Color::White,
+        }
+    }
+}
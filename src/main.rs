@@ -0,0 +1,20 @@
+#![warn(clippy::all, clippy::pedantic)]
+mod document;
+mod editor;
+mod filetype;
+mod highlighting;
+mod row;
+mod terminal;
+
+pub use document::Document;
+use editor::Editor;
+pub use editor::Position;
+pub use editor::SearchDirection;
+pub use filetype::FileType;
+pub use row::Row;
+pub use terminal::InputEvent;
+pub use terminal::Terminal;
+
+fn main() {
+    Editor::default().run();
+}
@@ -0,0 +1,406 @@
+use crate::highlighting;
+use crate::filetype::HighlightingOptions;
+use crate::SearchDirection;
+use std::cmp;
+use unicode_segmentation::UnicodeSegmentation;
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+fn is_separator(c: char) -> bool {
+    c.is_ascii_punctuation() || c.is_whitespace()
+}
+
+#[derive(Default)]
+pub struct Row {
+    string: String,
+    highlighting: Vec<highlighting::Type>,
+    len: usize,
+}
+
+impl From<&str> for Row {
+    fn from(slice: &str) -> Self {
+        let mut row = Self {
+            string: String::from(slice),
+            highlighting: Vec::new(),
+            len: 0,
+        };
+        row.update_len();
+        row
+    }
+}
+
+impl Row {
+    /// Renders the visible slice of this row between render columns `start`
+    /// and `end`, expanding tabs to the next `tab_stop` boundary.
+    #[must_use]
+    pub fn render(&self, start: usize, end: usize, tab_stop: usize) -> Vec<(String, highlighting::Type)> {
+        self.render_selected(start, end, tab_stop, None)
+            .into_iter()
+            .map(|(text, hl_type, _)| (text, hl_type))
+            .collect()
+    }
+
+    /// Like `render`, but also marks which chunks fall inside `selection`, a
+    /// render-column range (`start`, `end`) with `end` exclusive, so callers
+    /// can draw a Visual-mode selection highlight.
+    #[must_use]
+    pub fn render_selected(
+        &self,
+        start: usize,
+        end: usize,
+        tab_stop: usize,
+        selection: Option<(usize, usize)>,
+    ) -> Vec<(String, highlighting::Type, bool)> {
+        let mut result = Vec::new();
+        let mut rx = 0;
+        for (index, grapheme) in self.string[..].graphemes(true).enumerate() {
+            if rx >= end {
+                break;
+            }
+            if grapheme == "\t" {
+                let next_stop = rx + tab_stop.saturating_sub(rx % tab_stop);
+                let visible_start = cmp::max(start, rx);
+                if next_stop > visible_start {
+                    let selected = Self::in_selection(selection, visible_start, next_stop);
+                    result.push((" ".repeat(next_stop - visible_start), self.highlighting(index), selected));
+                }
+                rx = next_stop;
+            } else {
+                if rx >= start {
+                    let selected = Self::in_selection(selection, rx, rx + 1);
+                    result.push((grapheme.to_string(), self.highlighting(index), selected));
+                }
+                rx += 1;
+            }
+        }
+        result
+    }
+
+    fn in_selection(selection: Option<(usize, usize)>, chunk_start: usize, chunk_end: usize) -> bool {
+        selection.is_some_and(|(sel_start, sel_end)| chunk_start < sel_end && sel_start < chunk_end)
+    }
+
+    /// Converts a raw character index into the row to its render column,
+    /// accounting for tab expansion.
+    #[must_use]
+    pub fn cx_to_rx(&self, cx: usize, tab_stop: usize) -> usize {
+        let mut rx = 0;
+        for grapheme in self.string[..].graphemes(true).take(cx) {
+            if grapheme == "\t" {
+                rx += tab_stop.saturating_sub(rx % tab_stop);
+            } else {
+                rx += 1;
+            }
+        }
+        rx
+    }
+
+    /// Converts a render column back into the raw character index it falls
+    /// within, the inverse of `cx_to_rx`.
+    #[must_use]
+    pub fn rx_to_cx(&self, rx: usize, tab_stop: usize) -> usize {
+        let mut cur_rx = 0;
+        for (cx, grapheme) in self.string[..].graphemes(true).enumerate() {
+            let width = if grapheme == "\t" {
+                tab_stop.saturating_sub(cur_rx % tab_stop)
+            } else {
+                1
+            };
+            if cur_rx + width > rx {
+                return cx;
+            }
+            cur_rx += width;
+        }
+        self.len()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, at: usize, c: char) {
+        if at >= self.len() {
+            self.string.push(c);
+        } else {
+            let mut result: String = self.string[..].graphemes(true).take(at).collect();
+            let remainder: String = self.string[..].graphemes(true).skip(at).collect();
+            result.push(c);
+            result.push_str(&remainder);
+            self.string = result;
+        }
+        self.update_len();
+    }
+
+    pub fn delete(&mut self, at: usize) {
+        if at >= self.len() {
+            return;
+        }
+        let mut result: String = self.string[..].graphemes(true).take(at).collect();
+        let remainder: String = self.string[..].graphemes(true).skip(at + 1).collect();
+        result.push_str(&remainder);
+        self.string = result;
+        self.update_len();
+    }
+
+    pub fn append(&mut self, new: &Self) {
+        self.string = format!("{}{}", self.string, new.string);
+        self.update_len();
+    }
+
+    #[must_use]
+    pub fn split(&mut self, at: usize) -> Self {
+        let beginning: String = self.string[..].graphemes(true).take(at).collect();
+        let remainder: String = self.string[..].graphemes(true).skip(at).collect();
+        self.string = beginning;
+        self.update_len();
+        Self::from(&remainder[..])
+    }
+
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.string.as_bytes()
+    }
+
+    #[must_use]
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        self.string[..]
+            .graphemes(true)
+            .nth(index)
+            .and_then(|grapheme| grapheme.chars().next())
+    }
+
+    #[must_use]
+    pub fn find(&self, query: &str, at: usize, direction: SearchDirection) -> Option<usize> {
+        if at > self.len || query.is_empty() {
+            return None;
+        }
+        let start = if direction == SearchDirection::Forward {
+            at
+        } else {
+            0
+        };
+        let end = if direction == SearchDirection::Forward {
+            self.len
+        } else {
+            at
+        };
+        let substring: String = self.string[..]
+            .graphemes(true)
+            .skip(start)
+            .take(end - start)
+            .collect();
+        let matching_byte_index = if direction == SearchDirection::Forward {
+            substring.find(query)
+        } else {
+            substring.rfind(query)
+        };
+        if let Some(matching_byte_index) = matching_byte_index {
+            for (grapheme_index, (byte_index, _)) in substring[..].grapheme_indices(true).enumerate() {
+                if matching_byte_index == byte_index {
+                    return Some(start + grapheme_index);
+                }
+            }
+        }
+        None
+    }
+
+    #[must_use]
+    pub fn highlighting(&self, index: usize) -> highlighting::Type {
+        *self
+            .highlighting
+            .get(index)
+            .unwrap_or(&highlighting::Type::None)
+    }
+
+    pub fn highlight(&mut self, opts: &HighlightingOptions, word: Option<&str>) {
+        let chars: Vec<&str> = self.string.graphemes(true).collect();
+        let mut highlighting = Vec::new();
+
+        let mut matches = Vec::new();
+        let mut search_index = 0;
+        if let Some(word) = word {
+            while let Some(search_match) = self.find(word, search_index, SearchDirection::Forward) {
+                matches.push(search_match);
+                if let Some(next_index) = search_match.checked_add(word.graphemes(true).count()) {
+                    search_index = next_index;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        let mut prev_is_separator = true;
+        let mut in_string = false;
+        let mut index = 0;
+        while index < chars.len() {
+            let grapheme = chars[index];
+            let c = grapheme.chars().next().unwrap_or('\0');
+
+            if let Some(word) = word {
+                if !word.is_empty() && matches.contains(&index) {
+                    let len = word.graphemes(true).count();
+                    for _ in 0..len {
+                        highlighting.push(highlighting::Type::Match);
+                    }
+                    index += len;
+                    prev_is_separator = true;
+                    continue;
+                }
+            }
+
+            let previous_highlight = if index > 0 {
+                *highlighting.get(index - 1).unwrap_or(&highlighting::Type::None)
+            } else {
+                highlighting::Type::None
+            };
+
+            if opts.comments() && grapheme == "/" && chars.get(index + 1) == Some(&"/") {
+                for _ in index..chars.len() {
+                    highlighting.push(highlighting::Type::Comment);
+                }
+                break;
+            }
+
+            if opts.strings() {
+                if in_string {
+                    highlighting.push(highlighting::Type::String);
+                    if c == '\\' && chars.get(index + 1).is_some() {
+                        highlighting.push(highlighting::Type::String);
+                        index += 2;
+                        continue;
+                    }
+                    if c == '"' {
+                        in_string = false;
+                    }
+                    prev_is_separator = true;
+                    index += 1;
+                    continue;
+                } else if prev_is_separator && c == '"' {
+                    highlighting.push(highlighting::Type::String);
+                    in_string = true;
+                    index += 1;
+                    continue;
+                }
+            }
+
+            if opts.numbers()
+                && ((c.is_ascii_digit() && (prev_is_separator || previous_highlight == highlighting::Type::Number))
+                    || (c == '.' && previous_highlight == highlighting::Type::Number))
+            {
+                highlighting.push(highlighting::Type::Number);
+                prev_is_separator = false;
+                index += 1;
+                continue;
+            }
+
+            if opts.keywords() && prev_is_separator {
+                if let Some(word_len) = Self::match_keyword(&chars, index) {
+                    for _ in 0..word_len {
+                        highlighting.push(highlighting::Type::Keyword);
+                    }
+                    index += word_len;
+                    prev_is_separator = false;
+                    continue;
+                }
+            }
+
+            highlighting.push(highlighting::Type::None);
+            prev_is_separator = is_separator(c);
+            index += 1;
+        }
+
+        self.highlighting = highlighting;
+    }
+
+    fn match_keyword(chars: &[&str], index: usize) -> Option<usize> {
+        KEYWORDS.iter().find_map(|keyword| {
+            let len = keyword.graphemes(true).count();
+            if index + len > chars.len() {
+                return None;
+            }
+            if chars[index..index + len].concat() != *keyword {
+                return None;
+            }
+            let next_is_separator = chars
+                .get(index + len)
+                .is_none_or(|g| g.chars().next().is_none_or(is_separator));
+            if next_is_separator {
+                Some(len)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn update_len(&mut self) {
+        self.len = self.string[..].graphemes(true).count();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cx_to_rx_expands_tabs_to_the_next_stop() {
+        let row = Row::from("a\tbc\td");
+        assert_eq!(row.cx_to_rx(0, 4), 0); // 'a'
+        assert_eq!(row.cx_to_rx(1, 4), 1); // '\t', before expansion
+        assert_eq!(row.cx_to_rx(2, 4), 4); // 'b', tab padded out to the next stop
+        assert_eq!(row.cx_to_rx(4, 4), 6); // '\t' again, from column 6
+        assert_eq!(row.cx_to_rx(5, 4), 8); // 'd', padded out to the next stop
+    }
+
+    #[test]
+    fn cx_to_rx_on_tab_stop_boundary_does_not_pad() {
+        let row = Row::from("abcd\te");
+        assert_eq!(row.cx_to_rx(4, 4), 4); // already on a tab stop
+        assert_eq!(row.cx_to_rx(5, 4), 8); // tab still advances a full stop
+    }
+
+    #[test]
+    fn rx_to_cx_is_the_inverse_of_cx_to_rx() {
+        let row = Row::from("a\tbc");
+        for cx in 0..=row.len() {
+            let rx = row.cx_to_rx(cx, 4);
+            assert_eq!(row.rx_to_cx(rx, 4), cx);
+        }
+    }
+
+    #[test]
+    fn rx_to_cx_clamps_a_render_column_past_the_end_of_the_row() {
+        let row = Row::from("ab");
+        assert_eq!(row.rx_to_cx(100, 4), row.len());
+    }
+
+    #[test]
+    fn rx_to_cx_lands_on_the_tab_when_the_column_falls_inside_its_padding() {
+        let row = Row::from("a\tb");
+        // The tab spans render columns 1..4; any column in that range should
+        // resolve back to the tab's own character index, not the one after it.
+        assert_eq!(row.rx_to_cx(2, 4), 1);
+        assert_eq!(row.rx_to_cx(3, 4), 1);
+    }
+
+    #[test]
+    fn render_clips_to_the_requested_render_window() {
+        let row = Row::from("hello");
+        let rendered: String = row
+            .render(1, 4, 4)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect();
+        assert_eq!(rendered, "ell");
+    }
+}
@@ -1,8 +1,9 @@
 use crate::Position;
 use std::io::{self, Write};
-use crossterm::event::{Event, KeyEvent, DisableMouseCapture};
+use std::time::Duration;
+use crossterm::event::{Event, KeyEvent, MouseEvent, EnableMouseCapture, DisableMouseCapture};
 use crossterm::terminal::ClearType;
-use crossterm::style::{Print, SetForegroundColor, SetBackgroundColor, ResetColor, Color, Attribute};
+use crossterm::style::{SetForegroundColor, SetBackgroundColor, ResetColor, Color};
 use crossterm::{event, execute, terminal, cursor};
 
 pub struct Size {
@@ -14,9 +15,21 @@ pub struct Terminal {
     size: Size,
 }
 
+pub enum InputEvent {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+}
+
 impl Terminal {
-    pub fn default() -> Result<Self, std::io::Error> {
+    /// # Errors
+    /// Returns an error if enabling mouse capture fails.
+    ///
+    /// # Panics
+    /// Panics if raw mode cannot be enabled, since the editor cannot run
+    /// without it, or if the terminal size cannot be read.
+    pub fn new() -> Result<Self, std::io::Error> {
         terminal::enable_raw_mode().expect("Could not turn on Raw mode");
+        execute!(io::stdout(), EnableMouseCapture)?;
 
         let size = crossterm::terminal::size().unwrap();
         Ok(Self {
@@ -27,29 +40,40 @@ impl Terminal {
         })
     }
 
+    #[must_use]
     pub fn size(&self) -> &Size {
         &self.size
     }
-    
+
+    /// # Errors
+    /// Returns an error if moving the cursor fails.
     pub fn cursor_position(position: &Position) -> Result<(), std::io::Error> {
-        let Position{mut x, mut y} = position;
-        let x = x as u16;
-        let y = y as u16;
+        let Position { x, y } = position;
+        let x = u16::try_from(*x).unwrap_or(u16::MAX);
+        let y = u16::try_from(*y).unwrap_or(u16::MAX);
         execute!(io::stdout(), cursor::MoveTo(x, y))
     }
 
+    /// # Errors
+    /// Returns an error if clearing the screen fails.
     pub fn clear_screen() -> Result<(), std::io::Error> {
         execute!(io::stdout(), terminal::Clear(ClearType::All))
     }
 
+    /// # Errors
+    /// Returns an error if clearing the current line fails.
     pub fn clear_current_line() -> Result<(), std::io::Error> {
         execute!(io::stdout(), terminal::Clear(ClearType::CurrentLine))
     }
 
+    /// # Errors
+    /// Returns an error if flushing stdout fails.
     pub fn flush() -> Result<(), std::io::Error> {
         io::stdout().flush()
     }
-    
+
+    /// # Errors
+    /// Returns an error if reading from the terminal fails.
     pub fn read_key() -> Result<KeyEvent, std::io::Error> {
         loop {
             if let Ok(Event::Key(key_event)) = event::read() {
@@ -57,13 +81,64 @@ impl Terminal {
             }
         }
     }
+
+    /// # Errors
+    /// Returns an error if reading from the terminal fails.
+    pub fn read_event() -> Result<InputEvent, std::io::Error> {
+        loop {
+            match event::read()? {
+                Event::Key(key_event) => return Ok(InputEvent::Key(key_event)),
+                Event::Mouse(mouse_event) => return Ok(InputEvent::Mouse(mouse_event)),
+                _ => {},
+            }
+        }
+    }
+
+    /// Like `read_event`, but gives up and returns `Ok(None)` once `timeout`
+    /// elapses with no input, so callers can wake periodically instead of
+    /// blocking forever.
+    ///
+    /// # Errors
+    /// Returns an error if polling or reading from the terminal fails.
+    pub fn read_key_timeout(timeout: Duration) -> Result<Option<InputEvent>, std::io::Error> {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+        match event::read()? {
+            Event::Key(key_event) => Ok(Some(InputEvent::Key(key_event))),
+            Event::Mouse(mouse_event) => Ok(Some(InputEvent::Mouse(mouse_event))),
+            _ => Ok(None),
+        }
+    }
+
+    /// # Errors
+    /// Returns an error if setting the background color fails.
     pub fn set_background_color(color: Color) -> io::Result<()> {
         execute!(io::stdout(), SetBackgroundColor(color))
     }
+
+    /// # Errors
+    /// Returns an error if setting the foreground color fails.
     pub fn set_foreground_color(color: Color) -> io::Result<()> {
-        execute!(io::stdout(), SetForegroundColor(color)) 
+        execute!(io::stdout(), SetForegroundColor(color))
     }
+
+    /// # Errors
+    /// Returns an error if resetting the color fails.
     pub fn reset_color() -> io::Result<()> {
-        execute!(io::stdout(), ResetColor) 
+        execute!(io::stdout(), ResetColor)
     }
-}
\ No newline at end of file
+
+    /// # Errors
+    /// Returns an error if setting the cursor style fails.
+    pub fn set_cursor_style(style: cursor::SetCursorStyle) -> io::Result<()> {
+        execute!(io::stdout(), style)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        execute!(io::stdout(), DisableMouseCapture).unwrap_or_default();
+        terminal::disable_raw_mode().unwrap_or_default();
+    }
+}